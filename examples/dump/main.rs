@@ -13,19 +13,50 @@ async fn main() {
     let c = Client::new();
 
     call!(c, on_gce());
+    call!(c, probe_report());
+    call!(c, detect());
+    call!(c, is_shielded_vm());
+    call!(c, is_confidential_vm());
     call!(c, project_id());
+    call!(c, project_id_fresh());
+    println!("try_project_id = {:?}", c.try_project_id());
+    call!(c, project_id_arc());
     call!(c, numeric_project_id());
+    call!(c, numeric_project_id_fresh());
+    call!(c, numeric_project_id_u64());
     call!(c, internal_ip());
     call!(c, external_ip());
     call!(c, email(None));
     call!(c, hostname());
     call!(c, instance_tags());
     call!(c, instance_id());
+    call!(c, instance_id_fresh());
+    call!(c, instance_id_u64());
     call!(c, instance_name());
+    call!(c, description());
+    call!(c, drift_token());
     call!(c, zone());
+    call!(c, region());
+    call!(c, cluster_name());
+    call!(c, cluster_location());
+    call!(c, cluster_uid());
     call!(c, instance_attrs());
+    call!(c, instance_attrs_map());
     call!(c, project_attrs());
+    call!(c, project_attrs_map());
+    call!(c, default_region());
+    call!(c, default_zone());
     call!(c, instance_attr("attr"));
     call!(c, project_attr("attr"));
     call!(c, scopes(None));
+    call!(c, created_by());
+    call!(c, mig_info());
+    call!(c, container_declaration_raw());
+    call!(c, startup_script());
+    call!(c, startup_script_url());
+    call!(c, shutdown_script());
+    call!(c, instance_ssh_keys());
+    call!(c, project_ssh_keys());
+    call!(c, windows_keys());
+    call!(c, guest_attr_namespaces());
 }
@@ -0,0 +1,263 @@
+//! A blocking [`Client`](Client), for programs that do not run an async runtime.
+//!
+//! Every method waits for the corresponding method of the async
+//! [`Client`](crate::Client) on a runtime owned by the client. The methods must not be called
+//! from within an async runtime.
+//!
+//! # Example
+//! ```rust,no_run
+//! let client = gcemeta::blocking::Client::new()?;
+//! println!("project id = {:?}", client.project_id());
+//! # Ok::<(), gcemeta::Error>(())
+//! ```
+
+use std::{collections::HashMap, future::Future, sync::Arc, time::Duration};
+
+use http_body_util::Full;
+use hyper::{body::Bytes, http::uri::PathAndQuery, Response};
+use hyper_util::client::legacy::connect::HttpConnector;
+use tokio::runtime::{self, Runtime};
+
+#[cfg(feature = "cos")]
+use crate::ContainerDeclaration;
+use crate::{
+    AppEngineInfo, CacheStats, ClientBuilder, CloudFunctionsInfo, CloudRunInfo, Error, GetOptions,
+    MaybeStale, MigInfo, Platform, ProbeReport, Snapshot, SshKey, ValueWithEtag, WindowsKey,
+};
+
+macro_rules! forward {
+    ($($(#[$attr:meta])* fn $name:ident(&self $(, $arg:ident: $ty:ty)*) -> $ret:ty;)*) => {$(
+        $(#[$attr])*
+        #[doc = concat!(
+            "Blocking version of [`Client::", stringify!($name), "`](crate::Client::",
+            stringify!($name), ")."
+        )]
+        pub fn $name(&self $(, $arg: $ty)*) -> $ret {
+            self.block_on(self.inner.$name($($arg),*))
+        }
+    )*};
+}
+
+/// A blocking Client to access metadata service.
+#[derive(Clone, Debug)]
+pub struct Client {
+    inner: crate::Client<HttpConnector, Full<Bytes>>,
+    runtime: Arc<Runtime>,
+}
+
+impl Client {
+    /// Create a new Client with the default config.
+    pub fn new() -> crate::Result<Self> {
+        crate::Client::builder().build_blocking()
+    }
+
+    /// Create a [`ClientBuilder`](ClientBuilder) to configure a Client, which is built with
+    /// [`ClientBuilder::build_blocking`](ClientBuilder::build_blocking).
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::default()
+    }
+
+    pub(crate) fn with(inner: crate::Client<HttpConnector, Full<Bytes>>) -> crate::Result<Self> {
+        let runtime = runtime::Builder::new_current_thread().enable_all().build().map_err(|e| {
+            Error::Config { reason: format!("failed to start the blocking runtime: {}", e) }
+        })?;
+        Ok(Self { inner, runtime: Arc::new(runtime) })
+    }
+
+    fn block_on<F: Future>(&self, fut: F) -> F::Output {
+        self.runtime.block_on(fut)
+    }
+
+    /// Get value from the metadata service.
+    pub fn get(&self, path_and_query: PathAndQuery, trim: bool) -> crate::Result<String> {
+        self.block_on(self.inner.get(path_and_query, trim))
+    }
+
+    /// Get value from the metadata service together with the status and headers of its
+    /// response.
+    pub fn get_with_parts(
+        &self,
+        path_and_query: PathAndQuery,
+        trim: bool,
+    ) -> crate::Result<Response<String>> {
+        self.block_on(self.inner.get_with_parts(path_and_query, trim))
+    }
+
+    /// Get value from the metadata service, reporting whether it is a stale value served in place
+    /// of a failed request.
+    pub fn get_with_staleness(
+        &self,
+        path_and_query: PathAndQuery,
+        trim: bool,
+    ) -> crate::Result<MaybeStale<String>> {
+        self.block_on(self.inner.get_with_staleness(path_and_query, trim))
+    }
+
+    /// Get value from the metadata service together with its `ETag`.
+    pub fn get_with_etag(
+        &self,
+        path_and_query: PathAndQuery,
+        trim: bool,
+    ) -> crate::Result<ValueWithEtag> {
+        self.block_on(self.inner.get_with_etag(path_and_query, trim))
+    }
+
+    /// Get value from the metadata service, with the per-call `options`.
+    pub fn get_with_options(
+        &self,
+        path_and_query: PathAndQuery,
+        options: &GetOptions,
+    ) -> crate::Result<String> {
+        self.block_on(self.inner.get_with_options(path_and_query, options))
+    }
+
+    /// Get value from the metadata service, with `path` relative to `/computeMetadata/v1/`.
+    pub fn get_str(&self, path: &str, trim: bool) -> crate::Result<String> {
+        self.block_on(self.inner.get_str(path, trim))
+    }
+
+    /// Get the raw value from the metadata service, for values that are not UTF-8.
+    pub fn get_bytes(&self, path_and_query: PathAndQuery) -> crate::Result<Bytes> {
+        self.block_on(self.inner.get_bytes(path_and_query))
+    }
+
+    /// Get value from the metadata service, failing with [`Error::Timeout`](Error::Timeout) if
+    /// the whole request takes longer than `timeout`.
+    pub fn get_with_timeout(
+        &self,
+        path_and_query: PathAndQuery,
+        trim: bool,
+        timeout: Duration,
+    ) -> crate::Result<String> {
+        self.block_on(self.inner.get_with_timeout(path_and_query, trim, timeout))
+    }
+
+    /// Get value from the metadata service, or `None` if the key does not exist.
+    pub fn get_opt(
+        &self,
+        path_and_query: PathAndQuery,
+        trim: bool,
+    ) -> crate::Result<Option<String>> {
+        self.block_on(self.inner.get_opt(path_and_query, trim))
+    }
+
+    /// Get value from the metadata service, deserialized from JSON.
+    pub fn get_as<T>(&self, path_and_query: PathAndQuery) -> crate::Result<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.block_on(self.inner.get_as(path_and_query))
+    }
+
+    /// Get the value of the provided VM instance attribute, deserialized from JSON.
+    pub fn instance_attr_as<T>(&self, attr: impl AsRef<str>) -> crate::Result<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.block_on(self.inner.instance_attr_as(attr))
+    }
+
+    /// Get the value of the provided project attribute, deserialized from JSON.
+    pub fn project_attr_as<T>(&self, attr: impl AsRef<str>) -> crate::Result<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.block_on(self.inner.project_attr_as(attr))
+    }
+
+    /// See [`Client::try_project_id`](crate::Client::try_project_id).
+    pub fn try_project_id(&self) -> Option<String> {
+        self.inner.try_project_id()
+    }
+
+    /// See [`Client::try_numeric_project_id`](crate::Client::try_numeric_project_id).
+    pub fn try_numeric_project_id(&self) -> Option<String> {
+        self.inner.try_numeric_project_id()
+    }
+
+    /// See [`Client::try_instance_id`](crate::Client::try_instance_id).
+    pub fn try_instance_id(&self) -> Option<String> {
+        self.inner.try_instance_id()
+    }
+
+    /// Blocking version of [`Client::invalidate`](crate::Client::invalidate).
+    pub fn invalidate(&self, path: &PathAndQuery) {
+        self.block_on(self.inner.invalidate(path))
+    }
+
+    /// See [`Client::cache_stats`](crate::Client::cache_stats).
+    pub fn cache_stats(&self) -> HashMap<String, CacheStats> {
+        self.inner.cache_stats()
+    }
+
+    forward! {
+        fn prefetch_common(&self) -> crate::Result<()>;
+        fn snapshot(&self) -> Snapshot;
+        fn on_gce(&self) -> crate::Result<bool>;
+        fn reprobe_on_gce(&self) -> crate::Result<bool>;
+        fn probe_report(&self) -> ProbeReport;
+        fn detect(&self) -> crate::Result<Platform>;
+        fn is_shielded_vm(&self) -> crate::Result<Option<bool>>;
+        fn is_confidential_vm(&self) -> crate::Result<Option<bool>>;
+        fn project_id(&self) -> crate::Result<String>;
+        fn project_id_fresh(&self) -> crate::Result<String>;
+        fn project_id_arc(&self) -> crate::Result<Arc<str>>;
+        fn numeric_project_id(&self) -> crate::Result<String>;
+        fn numeric_project_id_fresh(&self) -> crate::Result<String>;
+        fn numeric_project_id_arc(&self) -> crate::Result<Arc<str>>;
+        fn numeric_project_id_u64(&self) -> crate::Result<u64>;
+        fn internal_ip(&self) -> crate::Result<String>;
+        fn external_ip(&self) -> crate::Result<String>;
+        fn email(&self, sa: Option<&str>) -> crate::Result<String>;
+        fn hostname(&self) -> crate::Result<String>;
+        fn instance_tags(&self) -> crate::Result<Vec<String>>;
+        fn instance_id(&self) -> crate::Result<String>;
+        fn instance_id_fresh(&self) -> crate::Result<String>;
+        fn instance_id_arc(&self) -> crate::Result<Arc<str>>;
+        fn instance_id_u64(&self) -> crate::Result<u64>;
+        fn instance_name(&self) -> crate::Result<String>;
+        fn description(&self) -> crate::Result<String>;
+        fn drift_token(&self) -> crate::Result<String>;
+        fn wait_for_drift_token_change(&self) -> crate::Result<String>;
+        fn zone(&self) -> crate::Result<String>;
+        fn region(&self) -> crate::Result<String>;
+        fn cloud_run_info(&self) -> crate::Result<CloudRunInfo>;
+        fn app_engine_info(&self) -> crate::Result<Option<AppEngineInfo>>;
+        fn cloud_functions_info(&self) -> crate::Result<Option<CloudFunctionsInfo>>;
+        fn cluster_name(&self) -> crate::Result<String>;
+        fn cluster_location(&self) -> crate::Result<String>;
+        fn cluster_uid(&self) -> crate::Result<String>;
+        fn instance_attrs(&self) -> crate::Result<Vec<String>>;
+        fn instance_attrs_map(&self) -> crate::Result<HashMap<String, String>>;
+        fn project_attrs(&self) -> crate::Result<Vec<String>>;
+        fn project_attrs_map(&self) -> crate::Result<HashMap<String, String>>;
+        fn default_region(&self) -> crate::Result<Option<String>>;
+        fn default_zone(&self) -> crate::Result<Option<String>>;
+        fn instance_attr(&self, attr: impl AsRef<str>) -> crate::Result<String>;
+        fn project_attr(&self, attr: impl AsRef<str>) -> crate::Result<String>;
+        fn scopes(&self, sa: Option<&str>) -> crate::Result<Vec<String>>;
+        fn created_by(&self) -> crate::Result<Option<String>>;
+        fn mig_info(&self) -> crate::Result<Option<MigInfo>>;
+        fn container_declaration_raw(&self) -> crate::Result<Option<String>>;
+        #[cfg(feature = "cos")]
+        fn container_declaration(&self) -> crate::Result<Option<ContainerDeclaration>>;
+        fn startup_script(&self) -> crate::Result<Option<String>>;
+        fn startup_script_url(&self) -> crate::Result<Option<String>>;
+        fn shutdown_script(&self) -> crate::Result<Option<String>>;
+        fn instance_ssh_keys(&self) -> crate::Result<Vec<SshKey>>;
+        fn project_ssh_keys(&self) -> crate::Result<Vec<SshKey>>;
+        fn windows_keys(&self) -> crate::Result<Vec<WindowsKey>>;
+        fn guest_attr_namespaces(&self) -> crate::Result<Vec<String>>;
+        fn delete_guest_attr(
+            &self,
+            namespace: impl AsRef<str>,
+            key: impl AsRef<str>
+        ) -> crate::Result<()>;
+        fn set_guest_attr(
+            &self,
+            namespace: impl AsRef<str>,
+            key: impl AsRef<str>,
+            value: impl Into<String>
+        ) -> crate::Result<()>;
+    }
+}
@@ -0,0 +1,82 @@
+use std::{
+    sync::{Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use tracing::trace;
+
+/// Counts consecutive transport failures of a client and its clones.
+#[derive(Default)]
+pub(crate) struct Breaker {
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    failures: u32,
+    open_until: Option<Instant>,
+}
+
+impl Breaker {
+    /// Report whether a request may be sent, i.e. the circuit is closed or its cooldown is over.
+    ///
+    /// Once the cooldown is over, a single failure opens the circuit again.
+    pub(crate) fn allow(&self) -> bool {
+        let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        !matches!(state.open_until, Some(until) if Instant::now() < until)
+    }
+
+    pub(crate) fn record(&self, success: bool, threshold: u32, cooldown: Duration) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        if success {
+            *state = State::default();
+            return;
+        }
+        state.failures = state.failures.saturating_add(1);
+        if state.failures >= threshold {
+            trace!("open circuit after {} consecutive failures", state.failures);
+            state.open_until = Some(Instant::now() + cooldown);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COOLDOWN: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn open_after_threshold() {
+        let breaker = Breaker::default();
+        breaker.record(false, 3, COOLDOWN);
+        breaker.record(false, 3, COOLDOWN);
+        assert!(breaker.allow());
+        breaker.record(false, 3, COOLDOWN);
+        assert!(!breaker.allow());
+    }
+
+    #[test]
+    fn success_resets() {
+        let breaker = Breaker::default();
+        breaker.record(false, 2, COOLDOWN);
+        breaker.record(true, 2, COOLDOWN);
+        breaker.record(false, 2, COOLDOWN);
+        assert!(breaker.allow());
+        breaker.record(false, 2, COOLDOWN);
+        assert!(!breaker.allow());
+        breaker.record(true, 2, COOLDOWN);
+        assert!(breaker.allow());
+    }
+
+    #[test]
+    fn half_open_after_cooldown() {
+        let breaker = Breaker::default();
+        breaker.record(false, 2, Duration::ZERO);
+        breaker.record(false, 2, Duration::ZERO);
+        assert!(breaker.allow());
+        // A single failure once the cooldown is over opens the circuit again.
+        breaker.record(false, 2, COOLDOWN);
+        assert!(!breaker.allow());
+    }
+}
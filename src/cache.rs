@@ -0,0 +1,49 @@
+use std::{future::Future, pin::Pin};
+
+use hyper::body::Bytes;
+
+/// An owned, boxed future, as returned by the methods of [`MetadataCache`](MetadataCache).
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// A store for the response bodies of the metadata service, keyed by path and query such as
+/// `/computeMetadata/v1/instance/zone`.
+///
+/// The [`Client`](crate::Client) looks every `GET` request up in the store before sending it,
+/// and puts every body it receives, including those of the cached getters such as
+/// [`Client::project_id`](crate::Client::project_id) when they do not have their value in
+/// memory. Set one with
+/// [`ClientBuilder::metadata_cache`](crate::ClientBuilder::metadata_cache), e.g. to share
+/// values between clients or to count hits. [`MemoryCache`](crate::MemoryCache) is the
+/// in-memory implementation used by [`ClientBuilder::path_cache`](crate::ClientBuilder::path_cache).
+pub trait MetadataCache: Send + Sync {
+    /// Look up the body stored for `path`.
+    fn get<'a>(&'a self, path: &'a str) -> BoxFuture<'a, Option<Bytes>>;
+
+    /// Store the body received for `path`.
+    fn put<'a>(&'a self, path: &'a str, body: Bytes) -> BoxFuture<'a, ()>;
+
+    /// Remove the body stored for `path`.
+    fn invalidate<'a>(&'a self, path: &'a str) -> BoxFuture<'a, ()>;
+
+    /// List the stored bodies, used by [`Client::snapshot`](crate::Client::snapshot).
+    ///
+    /// Returns nothing by default.
+    fn entries(&self) -> BoxFuture<'_, Vec<(String, Bytes)>> {
+        Box::pin(async { Vec::new() })
+    }
+}
+
+/// How often the value of a path was served from memory, see
+/// [`Client::cache_stats`](crate::Client::cache_stats).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Requests answered from memory.
+    pub hits: u64,
+    /// Requests sent to the metadata service because no value was cached.
+    pub misses: u64,
+    /// Requests sent to the metadata service to replace a cached value, such as by the `_fresh`
+    /// getters or [`Client::spawn_refresh`](crate::Client::spawn_refresh).
+    pub refreshes: u64,
+    /// Requests to the metadata service that failed.
+    pub errors: u64,
+}
@@ -0,0 +1,60 @@
+use serde::Deserialize;
+
+/// The container declaration of a Container-Optimized OS instance.
+///
+/// Parsed from the `gce-container-declaration` instance attribute. Google documents the format
+/// as not being a public API, so unknown fields are ignored.
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContainerDeclaration {
+    /// The declared pod spec.
+    #[serde(default)]
+    pub spec: ContainerSpec,
+}
+
+/// The pod spec of a [`ContainerDeclaration`](ContainerDeclaration).
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerSpec {
+    /// The containers to run.
+    #[serde(default)]
+    pub containers: Vec<Container>,
+    /// The restart policy, such as `Always`, `OnFailure` or `Never`.
+    #[serde(default)]
+    pub restart_policy: Option<String>,
+}
+
+/// A single container of a [`ContainerSpec`](ContainerSpec).
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Container {
+    /// The container name.
+    #[serde(default)]
+    pub name: String,
+    /// The container image.
+    pub image: String,
+    /// The entrypoint override.
+    #[serde(default)]
+    pub command: Vec<String>,
+    /// The arguments passed to the entrypoint.
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// The environment variables.
+    #[serde(default)]
+    pub env: Vec<EnvVar>,
+    /// Whether to allocate a buffer for stdin.
+    #[serde(default)]
+    pub stdin: bool,
+    /// Whether to allocate a pseudo-TTY.
+    #[serde(default)]
+    pub tty: bool,
+}
+
+/// An environment variable of a [`Container`](Container).
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct EnvVar {
+    /// The variable name.
+    pub name: String,
+    /// The variable value.
+    #[serde(default)]
+    pub value: String,
+}
@@ -0,0 +1,122 @@
+use std::{net::IpAddr, time::Duration};
+
+use tracing::trace;
+
+use crate::{rt::Rt, HickoryResolver, Resolve as _, Resolver};
+
+/// A signal used by [`Client::on_gce`](crate::Client::on_gce) to decide whether this process is
+/// running on Google Compute Engine.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Probe {
+    /// The `GCE_METADATA_HOST` environment variable is set.
+    Env,
+    /// The metadata IP answers with the `Metadata-Flavor: Google` header.
+    Http,
+    /// `metadata.google.internal` (or the configured host) resolves.
+    Dns,
+    /// The DMI (Linux) or SMBIOS (Windows) product name is `Google Compute Engine`.
+    SystemInfo,
+}
+
+/// Which probes [`Client::on_gce`](crate::Client::on_gce) runs and in what order.
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum DetectionStrategy {
+    /// Check the environment variable, then race the remaining probes.
+    #[default]
+    Default,
+    /// Only check the environment variable, e.g. for unit tests.
+    EnvOnly,
+    /// Only probe the metadata IP over HTTP, e.g. for containers where DNS is unreliable.
+    HttpOnly,
+    /// Run the given probes one after another until one of them succeeds.
+    ///
+    /// Each probe is bounded by the probe timeout.
+    Sequential(Vec<Probe>),
+}
+
+/// Resolve `host` with the resolver set by `ClientBuilder::build_with_resolver`, or else with a
+/// `HickoryResolver` reading the system configuration, so that no blocking thread is tied up.
+pub(crate) async fn probe_dns(
+    resolver: Option<&Resolver>,
+    host: &str,
+    expected: Option<IpAddr>,
+) -> bool {
+    let addrs = match resolver {
+        Some(resolver) => resolver.0.resolve(host).await,
+        None => match HickoryResolver::from_system_conf() {
+            Ok(resolver) => resolver.resolve(host).await,
+            Err(e) => Err(e),
+        },
+    };
+    let on = match addrs {
+        Ok(addrs) => match expected {
+            Some(ip) => addrs.contains(&ip),
+            None => !addrs.is_empty(),
+        },
+        Err(e) => {
+            trace!("resolve hostname error: {}", e);
+            false
+        }
+    };
+    trace!("resolve hostname: {}", on);
+    on
+}
+
+pub(crate) async fn probe_system_info(rt: &Rt) -> bool {
+    let on = rt.spawn_blocking(crate::host::system_product_name).await.unwrap_or(false);
+    trace!("check system product name: {}", on);
+    on
+}
+
+impl DetectionStrategy {
+    pub(crate) fn probes(&self) -> Vec<Probe> {
+        match self {
+            DetectionStrategy::Default => {
+                vec![Probe::Env, Probe::Http, Probe::Dns, Probe::SystemInfo]
+            }
+            DetectionStrategy::EnvOnly => vec![Probe::Env],
+            DetectionStrategy::HttpOnly => vec![Probe::Http],
+            DetectionStrategy::Sequential(probes) => probes.clone(),
+        }
+    }
+}
+
+/// The outcome of a single [`Probe`](Probe).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProbeStatus {
+    /// The probe found a GCE signal.
+    Positive,
+    /// The probe completed without finding a GCE signal.
+    Negative,
+    /// The probe did not complete within the probe timeout.
+    TimedOut,
+}
+
+/// A single probe run by [`Client::probe_report`](crate::Client::probe_report).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProbeOutcome {
+    /// The probe that was run.
+    pub probe: Probe,
+    /// How the probe ended.
+    pub status: ProbeStatus,
+    /// How long the probe took.
+    pub elapsed: Duration,
+}
+
+/// Describes how [`Client::on_gce`](crate::Client::on_gce) reaches its answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProbeReport {
+    /// The configured detection strategy.
+    pub strategy: DetectionStrategy,
+    /// The outcome of every probe of the strategy, in order.
+    pub outcomes: Vec<ProbeOutcome>,
+}
+
+impl ProbeReport {
+    /// Report whether any probe found a GCE signal.
+    pub fn on_gce(&self) -> bool {
+        self.outcomes.iter().any(|outcome| outcome.status == ProbeStatus::Positive)
+    }
+}
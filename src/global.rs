@@ -0,0 +1,60 @@
+//! Free functions backed by a process-global [`Client`](crate::Client).
+//!
+//! # Example
+//! ```rust,no_run
+//! async fn example() -> gcemeta::Result<()> {
+//!     if gcemeta::on_gce().await? {
+//!         println!("project id = {}", gcemeta::project_id().await?);
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use std::sync::OnceLock;
+
+use http_body_util::Full;
+use hyper::{body::Bytes, http::uri::PathAndQuery};
+use hyper_util::client::legacy::connect::HttpConnector;
+
+use crate::{Client, Platform};
+
+macro_rules! forward {
+    ($($(#[$attr:meta])* fn $name:ident($($arg:ident: $ty:ty),*) -> $ret:ty;)*) => {$(
+        $(#[$attr])*
+        #[doc = concat!(
+            "Call [`Client::", stringify!($name), "`](crate::Client::", stringify!($name),
+            ") on the [`default_client`](default_client)."
+        )]
+        pub async fn $name($($arg: $ty),*) -> $ret {
+            default_client().$name($($arg),*).await
+        }
+    )*};
+}
+
+/// Get the process-global Client, created with the default config on first use.
+///
+/// Its connection pool is driven by the tokio runtime that first sends a request, so
+/// programs that start and drop several runtimes should create their own Client instead.
+pub fn default_client() -> &'static Client<HttpConnector, Full<Bytes>> {
+    static CLIENT: OnceLock<Client<HttpConnector, Full<Bytes>>> = OnceLock::new();
+    CLIENT.get_or_init(Client::new)
+}
+
+forward! {
+    fn get(path_and_query: PathAndQuery, trim: bool) -> crate::Result<String>;
+    fn on_gce() -> crate::Result<bool>;
+    fn detect() -> crate::Result<Platform>;
+    fn project_id() -> crate::Result<String>;
+    fn numeric_project_id() -> crate::Result<String>;
+    fn instance_id() -> crate::Result<String>;
+    fn instance_name() -> crate::Result<String>;
+    fn hostname() -> crate::Result<String>;
+    fn internal_ip() -> crate::Result<String>;
+    fn external_ip() -> crate::Result<String>;
+    fn email(sa: Option<&str>) -> crate::Result<String>;
+    fn scopes(sa: Option<&str>) -> crate::Result<Vec<String>>;
+    fn zone() -> crate::Result<String>;
+    fn region() -> crate::Result<String>;
+    fn instance_attr(attr: impl AsRef<str>) -> crate::Result<String>;
+    fn project_attr(attr: impl AsRef<str>) -> crate::Result<String>;
+}
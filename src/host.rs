@@ -0,0 +1,89 @@
+//! Signals read from the local machine rather than the metadata service.
+
+use std::{fs, path::Path};
+
+/// Report whether the system product name identifies the machine as a GCE VM.
+///
+/// This reads the DMI product name on Linux and the SMBIOS product name from the registry on
+/// Windows.
+#[cfg(target_os = "linux")]
+pub(crate) fn system_product_name() -> bool {
+    fs::read_to_string("/sys/class/dmi/id/product_name")
+        .map(|s| is_gce_product_name(&s))
+        .unwrap_or(false)
+}
+
+#[cfg(windows)]
+pub(crate) fn system_product_name() -> bool {
+    use winreg::{enums::HKEY_LOCAL_MACHINE, RegKey};
+
+    RegKey::predef(HKEY_LOCAL_MACHINE)
+        .open_subkey(r"SYSTEM\HardwareConfig\Current")
+        .and_then(|key| key.get_value::<String, _>("SystemProductName"))
+        .map(|s| is_gce_product_name(&s))
+        .unwrap_or(false)
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+pub(crate) fn system_product_name() -> bool {
+    false
+}
+
+#[cfg(any(target_os = "linux", windows))]
+fn is_gce_product_name(s: &str) -> bool {
+    matches!(s.trim(), "Google" | "Google Compute Engine")
+}
+
+/// Report whether the machine boots with UEFI firmware and exposes a virtual TPM, or `None` if
+/// that cannot be told, e.g. in a container hiding `/sys/firmware`.
+#[cfg(target_os = "linux")]
+pub(crate) fn shielded_vm() -> Option<bool> {
+    let efi = if Path::new("/sys/firmware/efi").exists() {
+        Some(true)
+    } else {
+        // Container runtimes mask `/sys/firmware` with an empty directory.
+        has_entries("/sys/firmware").then_some(false)
+    };
+    let tpm = if has_entries("/sys/class/tpm")
+        || Path::new("/dev/tpm0").exists()
+        || Path::new("/dev/tpmrm0").exists()
+    {
+        Some(true)
+    } else {
+        Path::new("/sys/class").exists().then_some(false)
+    };
+    match (efi, tpm) {
+        (Some(false), _) | (_, Some(false)) => Some(false),
+        (Some(true), Some(true)) => Some(true),
+        _ => None,
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub(crate) fn shielded_vm() -> Option<bool> {
+    None
+}
+
+/// Report whether the machine runs with AMD SEV or Intel TDX memory encryption, or `None` if
+/// that cannot be told.
+#[cfg(target_os = "linux")]
+pub(crate) fn confidential_vm() -> Option<bool> {
+    if Path::new("/dev/sev-guest").exists() || Path::new("/dev/tdx_guest").exists() {
+        return Some(true);
+    }
+    // The CPU flags are those of the VM, also inside a container.
+    let cpuinfo = fs::read_to_string("/proc/cpuinfo").ok()?;
+    Some(cpuinfo.lines().filter(|line| line.starts_with("flags")).any(|line| {
+        line.split_whitespace().any(|flag| matches!(flag, "sev" | "sev_es" | "tdx_guest"))
+    }))
+}
+
+#[cfg(not(target_os = "linux"))]
+pub(crate) fn confidential_vm() -> Option<bool> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn has_entries(dir: &str) -> bool {
+    fs::read_dir(dir).map(|mut entries| entries.next().is_some()).unwrap_or(false)
+}
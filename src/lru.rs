@@ -0,0 +1,174 @@
+//! A small least-recently-used cache of response bodies keyed by path.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use hyper::body::Bytes;
+
+use crate::{BoxFuture, MetadataCache};
+
+/// A [`MetadataCache`](MetadataCache) keeping up to a fixed number of bodies in memory, each
+/// for a fixed time.
+///
+/// The least recently used body is evicted first.
+pub struct MemoryCache {
+    capacity: usize,
+    ttl: Duration,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    entries: HashMap<String, Entry>,
+    // Incremented on every access, the entry with the smallest `used` is evicted first.
+    clock: u64,
+    evictions: u64,
+}
+
+struct Entry {
+    body: Bytes,
+    at: Instant,
+    used: u64,
+}
+
+impl MemoryCache {
+    /// Create a cache holding up to `capacity` bodies, each for `ttl`.
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self { capacity: capacity.max(1), ttl, state: Default::default() }
+    }
+
+    /// Report how many bodies were evicted to make room for another one.
+    pub fn evictions(&self) -> u64 {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner).evictions
+    }
+
+    pub(crate) fn lookup(&self, key: &str) -> Option<Bytes> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.clock += 1;
+        let clock = state.clock;
+        match state.entries.get_mut(key) {
+            Some(entry) if entry.at.elapsed() < self.ttl => {
+                entry.used = clock;
+                Some(entry.body.clone())
+            }
+            Some(_) => {
+                state.entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub(crate) fn insert(&self, key: &str, body: Bytes) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.clock += 1;
+        let used = state.clock;
+        if !state.entries.contains_key(key) && state.entries.len() >= self.capacity {
+            let ttl = self.ttl;
+            state.entries.retain(|_, entry| entry.at.elapsed() < ttl);
+            if state.entries.len() >= self.capacity {
+                let oldest = state.entries.iter().min_by_key(|(_, entry)| entry.used);
+                if let Some(oldest) = oldest.map(|(key, _)| key.clone()) {
+                    state.entries.remove(&oldest);
+                    state.evictions += 1;
+                }
+            }
+        }
+        state.entries.insert(key.to_owned(), Entry { body, at: Instant::now(), used });
+    }
+
+    /// The bodies that have not expired yet.
+    pub(crate) fn entries(&self) -> Vec<(String, Bytes)> {
+        let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.at.elapsed() < self.ttl)
+            .map(|(key, entry)| (key.clone(), entry.body.clone()))
+            .collect()
+    }
+}
+
+impl MetadataCache for MemoryCache {
+    fn get<'a>(&'a self, path: &'a str) -> BoxFuture<'a, Option<Bytes>> {
+        let body = self.lookup(path);
+        Box::pin(async { body })
+    }
+
+    fn put<'a>(&'a self, path: &'a str, body: Bytes) -> BoxFuture<'a, ()> {
+        self.insert(path, body);
+        Box::pin(async {})
+    }
+
+    fn invalidate<'a>(&'a self, path: &'a str) -> BoxFuture<'a, ()> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner).entries.remove(path);
+        Box::pin(async {})
+    }
+
+    fn entries(&self) -> BoxFuture<'_, Vec<(String, Bytes)>> {
+        let entries = MemoryCache::entries(self);
+        Box::pin(async { entries })
+    }
+}
+
+impl fmt::Debug for MemoryCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemoryCache")
+            .field("capacity", &self.capacity)
+            .field("ttl", &self.ttl)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TTL: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn evict_least_recently_used() {
+        let cache = MemoryCache::new(2, TTL);
+        cache.insert("a", Bytes::from_static(b"1"));
+        cache.insert("b", Bytes::from_static(b"2"));
+        // Reading `a` keeps it, `b` is now the least recently used.
+        assert_eq!(cache.lookup("a"), Some(Bytes::from_static(b"1")));
+        cache.insert("c", Bytes::from_static(b"3"));
+        assert_eq!(cache.lookup("b"), None);
+        assert_eq!(cache.lookup("a"), Some(Bytes::from_static(b"1")));
+        assert_eq!(cache.lookup("c"), Some(Bytes::from_static(b"3")));
+        assert_eq!(cache.evictions(), 1);
+    }
+
+    #[test]
+    fn replace_without_eviction() {
+        let cache = MemoryCache::new(1, TTL);
+        cache.insert("a", Bytes::from_static(b"1"));
+        cache.insert("a", Bytes::from_static(b"2"));
+        assert_eq!(cache.lookup("a"), Some(Bytes::from_static(b"2")));
+        assert_eq!(cache.evictions(), 0);
+    }
+
+    #[test]
+    fn expire() {
+        let cache = MemoryCache::new(1, Duration::ZERO);
+        cache.insert("a", Bytes::from_static(b"1"));
+        assert_eq!(cache.lookup("a"), None);
+        assert!(cache.entries().is_empty());
+        // Expired entries make room without counting as evictions.
+        cache.insert("b", Bytes::from_static(b"2"));
+        assert_eq!(cache.evictions(), 0);
+    }
+
+    #[test]
+    fn invalidate() {
+        let cache = MemoryCache::new(2, TTL);
+        cache.insert("a", Bytes::from_static(b"1"));
+        drop(MetadataCache::invalidate(&cache, "a"));
+        assert_eq!(cache.lookup("a"), None);
+    }
+}
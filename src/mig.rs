@@ -0,0 +1,93 @@
+/// Information about the managed instance group that created this VM.
+///
+/// Parsed from the `created-by` instance attribute, which holds a resource name of the form
+/// `projects/<project>/zones/<zone>/instanceGroupManagers/<name>` or
+/// `projects/<project>/regions/<region>/instanceGroupManagers/<name>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigInfo {
+    /// The project of the group, usually as a numeric project ID.
+    pub project: String,
+    /// The zone of a zonal group.
+    pub zone: Option<String>,
+    /// The region of a regional group.
+    pub region: Option<String>,
+    /// The name of the group.
+    pub group_name: String,
+}
+
+impl MigInfo {
+    /// Parse a managed instance group resource name.
+    ///
+    /// Returns `None` if the name does not refer to an instance group manager.
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        let s = match s.find("projects/") {
+            Some(i) if i == 0 || s[..i].ends_with('/') => &s[i + "projects/".len()..],
+            _ => return None,
+        };
+        let parts: Vec<_> = s.split('/').collect();
+        match parts.as_slice() {
+            [project, kind, location, "instanceGroupManagers", name]
+                if !project.is_empty() && !location.is_empty() && !name.is_empty() =>
+            {
+                let (zone, region) = match *kind {
+                    "zones" => (Some(location.to_string()), None),
+                    "regions" => (None, Some(location.to_string())),
+                    _ => return None,
+                };
+                Some(Self {
+                    project: project.to_string(),
+                    zone,
+                    region,
+                    group_name: name.to_string(),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            MigInfo::parse("projects/123/zones/us-central1-a/instanceGroupManagers/web"),
+            Some(MigInfo {
+                project: "123".into(),
+                zone: Some("us-central1-a".into()),
+                region: None,
+                group_name: "web".into(),
+            })
+        );
+        assert_eq!(
+            MigInfo::parse(" projects/123/regions/us-central1/instanceGroupManagers/web\n"),
+            Some(MigInfo {
+                project: "123".into(),
+                zone: None,
+                region: Some("us-central1".into()),
+                group_name: "web".into(),
+            })
+        );
+        let url =
+            "https://www.googleapis.com/compute/v1/projects/p/zones/z/instanceGroupManagers/g";
+        assert_eq!(MigInfo::parse(url).map(|mig| mig.group_name), Some("g".into()));
+    }
+
+    #[test]
+    fn parse_invalid() {
+        for s in [
+            "",
+            "projects/123/zones/us-central1-a/instances/vm",
+            "projects/123/global/x/instanceGroupManagers/web",
+            "projects//zones/z/instanceGroupManagers/web",
+            "projects/123/zones/z/instanceGroupManagers/",
+            "projects/123/zones/z/instanceGroupManagers/web/extra",
+            "myprojects/123/zones/z/instanceGroupManagers/web",
+        ] {
+            assert_eq!(MigInfo::parse(s), None, "{}", s);
+        }
+    }
+}
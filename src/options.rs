@@ -0,0 +1,124 @@
+//! Per-call options of a `GET` request.
+
+use std::{str::FromStr, time::Duration};
+
+use hyper::http::uri::PathAndQuery;
+
+use crate::Error;
+
+/// The format of a directory value, see [`GetOptions::alt`](GetOptions::alt).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Alt {
+    /// A JSON object, `alt=json`.
+    Json,
+    /// One entry per line, `alt=text`.
+    Text,
+}
+
+/// Per-call options of [`Client::get_with_options`](crate::Client::get_with_options).
+///
+/// The defaults match [`Client::get`](crate::Client::get) with `trim` unset.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GetOptions {
+    pub(crate) trim: bool,
+    recursive: bool,
+    alt: Option<Alt>,
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) no_cache: bool,
+}
+
+impl GetOptions {
+    /// Create the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Trim the whitespace around the value.
+    ///
+    /// Disabled by default.
+    pub fn trim(mut self, enabled: bool) -> Self {
+        self.trim = enabled;
+        self
+    }
+
+    /// Get the contents of a directory and all its subdirectories, with `recursive=true`.
+    ///
+    /// Disabled by default.
+    pub fn recursive(mut self, enabled: bool) -> Self {
+        self.recursive = enabled;
+        self
+    }
+
+    /// Set the format of the value with the `alt` parameter.
+    ///
+    /// Defaults to the format chosen by the metadata service.
+    pub fn alt(mut self, alt: Alt) -> Self {
+        self.alt = Some(alt);
+        self
+    }
+
+    /// Fail with [`Error::Timeout`](Error::Timeout) if the whole request, including retries
+    /// and reading the body, takes longer than `timeout`.
+    ///
+    /// Defaults to no limit but the [`request_timeout`](crate::ClientBuilder::request_timeout).
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Allow the value to be answered from the values kept in memory or the
+    /// [`path_cache`](crate::ClientBuilder::path_cache). When disabled, the metadata service is
+    /// always asked, and a failure is returned even with
+    /// [`serve_stale_on_error`](crate::ClientBuilder::serve_stale_on_error), like the `_fresh`
+    /// getters do.
+    ///
+    /// Enabled by default.
+    pub fn cache(mut self, enabled: bool) -> Self {
+        self.no_cache = !enabled;
+        self
+    }
+
+    /// Add the query parameters of the options to `path_and_query`.
+    pub(crate) fn apply(&self, path_and_query: PathAndQuery) -> crate::Result<PathAndQuery> {
+        if !self.recursive && self.alt.is_none() {
+            return Ok(path_and_query);
+        }
+        let mut path = path_and_query.as_str().to_owned();
+        let mut sep = if path_and_query.query().is_some() { '&' } else { '?' };
+        if self.recursive {
+            path.push(sep);
+            path.push_str("recursive=true");
+            sep = '&';
+        }
+        if let Some(alt) = self.alt {
+            path.push(sep);
+            path.push_str(match alt {
+                Alt::Json => "alt=json",
+                Alt::Text => "alt=text",
+            });
+        }
+        PathAndQuery::from_str(&path).map_err(|source| Error::Uri { path, source })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(options: GetOptions, path: &'static str) -> String {
+        options.apply(PathAndQuery::from_static(path)).unwrap().to_string()
+    }
+
+    #[test]
+    fn apply_query() {
+        assert_eq!(apply(GetOptions::new(), "/a"), "/a");
+        assert_eq!(apply(GetOptions::new().trim(true).cache(false), "/a"), "/a");
+        assert_eq!(apply(GetOptions::new().recursive(true), "/a/"), "/a/?recursive=true");
+        assert_eq!(apply(GetOptions::new().alt(Alt::Text), "/a"), "/a?alt=text");
+        assert_eq!(
+            apply(GetOptions::new().recursive(true).alt(Alt::Json), "/a/"),
+            "/a/?recursive=true&alt=json"
+        );
+        assert_eq!(apply(GetOptions::new().alt(Alt::Json), "/a?x=1"), "/a?x=1&alt=json");
+    }
+}
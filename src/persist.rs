@@ -0,0 +1,129 @@
+//! Keeps the values that never change during the lifetime of an instance in a file, see
+//! `ClientBuilder::persist_cache`.
+
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::PathBuf,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use hyper::body::Bytes;
+use serde::{Deserialize, Serialize};
+use tracing::trace;
+
+use crate::{rt::Rt, Snapshot};
+
+/// The paths that are persisted.
+const PATHS: [&str; 4] = [
+    "/computeMetadata/v1/project/project-id",
+    "/computeMetadata/v1/project/numeric-project-id",
+    "/computeMetadata/v1/instance/id",
+    "/computeMetadata/v1/instance/zone",
+];
+
+/// The content of the file.
+#[derive(Serialize, Deserialize)]
+struct File {
+    /// Identifies the host the values were received on, see [`host_key`](host_key).
+    host_key: String,
+    #[serde(flatten)]
+    snapshot: Snapshot,
+}
+
+/// Identify the running host, so that a file baked into an image or kept on a disk attached to
+/// another instance is not trusted.
+///
+/// The boot ID changes on every boot, the values are requested again after a reboot.
+fn host_key() -> Option<String> {
+    #[cfg(target_os = "linux")]
+    let key = fs::read_to_string("/proc/sys/kernel/random/boot_id").ok();
+    #[cfg(windows)]
+    let key = std::env::var("COMPUTERNAME").ok();
+    #[cfg(not(any(target_os = "linux", windows)))]
+    let key = std::env::var("HOSTNAME").ok();
+    key.map(|key| key.trim().to_owned()).filter(|key| !key.is_empty())
+}
+
+pub(crate) struct Store {
+    path: PathBuf,
+    host_key: String,
+    values: Mutex<BTreeMap<String, String>>,
+    // Held while writing, so that concurrent writes do not interleave.
+    writing: Mutex<()>,
+}
+
+impl Store {
+    /// Read the file, a missing or unreadable file, or one written on another host, is treated
+    /// as empty.
+    ///
+    /// Returns `None` if the host cannot be identified, nothing is persisted then.
+    pub(crate) fn load(path: PathBuf) -> Option<Self> {
+        let host_key = match host_key() {
+            Some(key) => key,
+            None => {
+                trace!("persisted cache `{}` disabled: unknown host", path.display());
+                return None;
+            }
+        };
+        let file = fs::read(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|s| serde_json::from_slice::<File>(&s).map_err(|e| e.to_string()));
+        let values = match file {
+            Ok(file) if file.host_key == host_key => file.snapshot.values,
+            Ok(_) => {
+                trace!("persisted cache `{}` ignored: written on another host", path.display());
+                Default::default()
+            }
+            Err(e) => {
+                trace!("read persisted cache `{}` error: {}", path.display(), e);
+                Default::default()
+            }
+        };
+        let values = values.into_iter().filter(|(key, _)| PATHS.contains(&key.as_str())).collect();
+        Some(Self { path, host_key, values: Mutex::new(values), writing: Mutex::new(()) })
+    }
+
+    pub(crate) fn values(&self) -> BTreeMap<String, String> {
+        self.values.lock().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    /// Remember the body received for `key` and rewrite the file on the blocking pool of `rt`
+    /// if it changed.
+    pub(crate) fn record(self: &Arc<Self>, rt: &Rt, key: &str, body: &Bytes) {
+        if !PATHS.contains(&key) {
+            return;
+        }
+        let value = match std::str::from_utf8(body) {
+            Ok(value) => value,
+            Err(_) => return,
+        };
+        {
+            let mut values = self.values.lock().unwrap_or_else(PoisonError::into_inner);
+            if values.get(key).map(String::as_str) == Some(value) {
+                return;
+            }
+            values.insert(key.to_owned(), value.to_owned());
+        }
+        let store = self.clone();
+        drop(rt.spawn_blocking(move || {
+            if let Err(e) = store.write() {
+                trace!("write persisted cache `{}` error: {}", store.path.display(), e);
+            }
+        }));
+    }
+
+    /// Write the current values, a later write always sees the values of an earlier one.
+    fn write(&self) -> io::Result<()> {
+        let _writing = self.writing.lock().unwrap_or_else(PoisonError::into_inner);
+        let file = File {
+            host_key: self.host_key.clone(),
+            snapshot: Snapshot { on_gce: None, values: self.values() },
+        };
+        // Write to a temporary file first, so a crash never leaves a truncated file behind.
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".tmp");
+        fs::write(&tmp, serde_json::to_vec(&file)?)?;
+        fs::rename(&tmp, &self.path)
+    }
+}
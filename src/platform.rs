@@ -0,0 +1,164 @@
+use std::env;
+
+/// The Google compute environment this process is running on.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Platform {
+    /// Google Compute Engine.
+    ComputeEngine,
+    /// Google Kubernetes Engine.
+    KubernetesEngine,
+    /// Cloud Run services and jobs.
+    CloudRun,
+    /// Cloud Functions.
+    CloudFunctions,
+    /// App Engine standard environment.
+    AppEngineStandard,
+    /// App Engine flexible environment.
+    AppEngineFlexible,
+    /// Not running on Google Cloud.
+    NotGcp,
+}
+
+impl Platform {
+    /// Report whether the platform is one of the Google Cloud environments.
+    pub fn is_gcp(self) -> bool {
+        self != Platform::NotGcp
+    }
+}
+
+/// Identity of a Cloud Run service or job.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CloudRunInfo {
+    /// The service name, from `K_SERVICE`.
+    pub service: Option<String>,
+    /// The revision name, from `K_REVISION`.
+    pub revision: Option<String>,
+    /// The configuration name, from `K_CONFIGURATION`.
+    pub configuration: Option<String>,
+    /// The job name, from `CLOUD_RUN_JOB`.
+    pub job: Option<String>,
+    /// The execution name, from `CLOUD_RUN_EXECUTION`.
+    pub execution: Option<String>,
+    /// The project ID.
+    pub project_id: String,
+    /// The region, such as `us-central1`.
+    pub region: String,
+    /// The email of the service account the workload runs as.
+    pub service_account: String,
+}
+
+/// Identity of an App Engine service.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppEngineInfo {
+    /// Whether this is the flexible environment rather than the standard one.
+    pub flexible: bool,
+    /// The service name, from `GAE_SERVICE`.
+    pub service: String,
+    /// The version name, from `GAE_VERSION`.
+    pub version: String,
+    /// The instance ID, from `GAE_INSTANCE`.
+    pub instance: Option<String>,
+    /// The project ID.
+    pub project_id: String,
+    /// The zone the instance is running in.
+    pub zone: String,
+}
+
+/// Identity of a Cloud Function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CloudFunctionsInfo {
+    /// The function name, from `K_SERVICE` or `FUNCTION_NAME` on older runtimes.
+    pub name: Option<String>,
+    /// The function executed on invocation, from `FUNCTION_TARGET`.
+    pub target: Option<String>,
+    /// The region, from `FUNCTION_REGION` or the metadata service.
+    pub region: Option<String>,
+}
+
+pub(crate) fn var(key: &str) -> Option<String> {
+    env::var(key).ok().filter(|v| !v.is_empty())
+}
+
+fn present(key: &str) -> bool {
+    env::var_os(key).map(|v| !v.is_empty()).unwrap_or(false)
+}
+
+/// Guess the platform from the environment variables set by each serverless runtime.
+///
+/// Returns `None` if none of them is set, in which case the platform is either Compute Engine,
+/// Kubernetes Engine or not Google Cloud at all.
+pub(crate) fn from_env() -> Option<Platform> {
+    // https://cloud.google.com/functions/docs/configuring/env-var#runtime_environment_variables_set_automatically
+    // 2nd gen functions also set `K_SERVICE`, so this must come before the Cloud Run check.
+    if present("FUNCTION_TARGET") || present("FUNCTION_NAME") {
+        return Some(Platform::CloudFunctions);
+    }
+    // https://cloud.google.com/run/docs/container-contract#env-vars
+    if (present("K_SERVICE") && present("K_CONFIGURATION")) || present("CLOUD_RUN_JOB") {
+        return Some(Platform::CloudRun);
+    }
+    // https://cloud.google.com/appengine/docs/standard/runtimes
+    if present("GAE_SERVICE") || present("GAE_APPLICATION") {
+        return Some(match env::var("GAE_ENV").as_deref() {
+            Ok("standard") => Platform::AppEngineStandard,
+            _ => Platform::AppEngineFlexible,
+        });
+    }
+    None
+}
+
+/// Report whether this process is running inside a Kubernetes pod.
+pub(crate) fn in_kubernetes() -> bool {
+    present("KUBERNETES_SERVICE_HOST")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VARS: [&str; 8] = [
+        "FUNCTION_TARGET",
+        "FUNCTION_NAME",
+        "K_SERVICE",
+        "K_CONFIGURATION",
+        "CLOUD_RUN_JOB",
+        "GAE_SERVICE",
+        "GAE_APPLICATION",
+        "GAE_ENV",
+    ];
+
+    fn from_vars(vars: &[(&str, &str)]) -> Option<Platform> {
+        for key in VARS {
+            env::remove_var(key);
+        }
+        for (key, value) in vars {
+            env::set_var(key, value);
+        }
+        from_env()
+    }
+
+    // The environment is shared by the whole process, so every case runs in this one test.
+    #[test]
+    fn detect_from_env() {
+        assert_eq!(from_vars(&[]), None);
+        assert_eq!(from_vars(&[("K_SERVICE", "")]), None);
+        assert_eq!(from_vars(&[("K_SERVICE", "s")]), None);
+        assert_eq!(
+            from_vars(&[("K_SERVICE", "s"), ("K_CONFIGURATION", "c")]),
+            Some(Platform::CloudRun)
+        );
+        assert_eq!(from_vars(&[("CLOUD_RUN_JOB", "j")]), Some(Platform::CloudRun));
+        assert_eq!(
+            from_vars(&[("K_SERVICE", "s"), ("K_CONFIGURATION", "c"), ("FUNCTION_TARGET", "f")]),
+            Some(Platform::CloudFunctions)
+        );
+        assert_eq!(from_vars(&[("FUNCTION_NAME", "f")]), Some(Platform::CloudFunctions));
+        assert_eq!(
+            from_vars(&[("GAE_SERVICE", "default"), ("GAE_ENV", "standard")]),
+            Some(Platform::AppEngineStandard)
+        );
+        assert_eq!(from_vars(&[("GAE_APPLICATION", "a")]), Some(Platform::AppEngineFlexible));
+        from_vars(&[]);
+    }
+}
@@ -0,0 +1,83 @@
+//! Custom DNS resolvers, for the DNS probe and for connections to the metadata host.
+
+use std::{
+    fmt, io,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use hyper_util::client::legacy::connect::dns::Name;
+
+use crate::BoxFuture;
+
+/// A DNS resolver, set with
+/// [`ClientBuilder::build_with_resolver`](crate::ClientBuilder::build_with_resolver).
+pub trait Resolve: Send + Sync {
+    /// Look up the IP addresses of `host`.
+    fn resolve(&self, host: &str) -> BoxFuture<'static, io::Result<Vec<IpAddr>>>;
+}
+
+/// A shared [`Resolve`](Resolve), used by the http client of a Client built with
+/// [`ClientBuilder::build_with_resolver`](crate::ClientBuilder::build_with_resolver).
+#[derive(Clone)]
+pub struct Resolver(pub(crate) Arc<dyn Resolve>);
+
+impl fmt::Debug for Resolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Resolver").finish()
+    }
+}
+
+impl tower_service::Service<Name> for Resolver {
+    type Response = std::vec::IntoIter<SocketAddr>;
+    type Error = io::Error;
+    type Future = BoxFuture<'static, io::Result<Self::Response>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, name: Name) -> Self::Future {
+        let fut = self.0.resolve(name.as_str());
+        Box::pin(async move {
+            // the connector fills in the port
+            let addrs = fut.await?.into_iter().map(|ip| SocketAddr::new(ip, 0));
+            Ok(addrs.collect::<Vec<_>>().into_iter())
+        })
+    }
+}
+
+/// A [`Resolve`](Resolve) backed by hickory-dns.
+#[derive(Clone)]
+pub struct HickoryResolver(hickory_resolver::TokioResolver);
+
+impl HickoryResolver {
+    /// Create a resolver reading the system configuration, `/etc/resolv.conf` on Unix.
+    pub fn from_system_conf() -> io::Result<Self> {
+        let builder = hickory_resolver::TokioResolver::builder_tokio().map_err(io::Error::other)?;
+        Ok(Self(builder.build()))
+    }
+}
+
+impl From<hickory_resolver::TokioResolver> for HickoryResolver {
+    fn from(resolver: hickory_resolver::TokioResolver) -> Self {
+        Self(resolver)
+    }
+}
+
+impl fmt::Debug for HickoryResolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HickoryResolver").finish()
+    }
+}
+
+impl Resolve for HickoryResolver {
+    fn resolve(&self, host: &str) -> BoxFuture<'static, io::Result<Vec<IpAddr>>> {
+        let (resolver, host) = (self.0.clone(), host.to_owned());
+        Box::pin(async move {
+            let lookup = resolver.lookup_ip(host).await.map_err(io::Error::other)?;
+            Ok(lookup.iter().collect())
+        })
+    }
+}
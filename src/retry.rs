@@ -0,0 +1,197 @@
+use std::time::Duration;
+
+use hyper::StatusCode;
+
+use crate::Error;
+
+/// How failed `GET` requests are retried by the [`Client`](crate::Client).
+///
+/// Retries are disabled unless a policy is set with
+/// [`ClientBuilder::retry_policy`](crate::ClientBuilder::retry_policy). The delay before the
+/// `n`-th retry is `base_delay * multiplier^(n - 1)`, capped at `max_delay`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub(crate) max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    multiplier: u32,
+    jitter: bool,
+    retry_after: bool,
+    statuses: Vec<StatusCode>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(5),
+            multiplier: 2,
+            jitter: true,
+            retry_after: true,
+            statuses: vec![
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Set how many times a request is sent at most, including the first attempt.
+    ///
+    /// Defaults to 3.
+    pub fn max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts.max(1);
+        self
+    }
+
+    /// Set the delay before the first retry.
+    ///
+    /// Defaults to 100 milliseconds.
+    pub fn base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+        self
+    }
+
+    /// Set the upper bound of the delay between two attempts.
+    ///
+    /// Defaults to 5 seconds.
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Set the factor the delay grows by after every retry.
+    ///
+    /// Defaults to 2.
+    pub fn multiplier(mut self, multiplier: u32) -> Self {
+        self.multiplier = multiplier.max(1);
+        self
+    }
+
+    /// Randomize every delay between half and the full computed value, so that clients started
+    /// together do not retry in lockstep.
+    ///
+    /// Enabled by default.
+    pub fn jitter(mut self, enabled: bool) -> Self {
+        self.jitter = enabled;
+        self
+    }
+
+    /// Wait as long as the `Retry-After` header of a `429` or `503` response asks for instead of
+    /// the computed delay.
+    ///
+    /// A response asking for a longer wait than the [`max_delay`](RetryPolicy::max_delay) is
+    /// not retried, its error is returned.
+    ///
+    /// Enabled by default.
+    pub fn respect_retry_after(mut self, enabled: bool) -> Self {
+        self.retry_after = enabled;
+        self
+    }
+
+    /// Set the response statuses that are retried.
+    ///
+    /// Defaults to `429`, `500`, `502`, `503` and `504`. Connection failures and timeouts are
+    /// always retried.
+    pub fn retryable_statuses(mut self, statuses: impl IntoIterator<Item = StatusCode>) -> Self {
+        self.statuses = statuses.into_iter().collect();
+        self
+    }
+
+    pub(crate) fn should_retry(&self, err: &Error) -> bool {
+        match err.status() {
+            Some(status) => self.statuses.contains(&status),
+            None => err.is_retryable(),
+        }
+    }
+
+    /// The delay before retrying the given attempt, starting at 1, which failed with `err`, or
+    /// `None` if the response asks for a longer wait than the `max_delay`.
+    pub(crate) fn delay(&self, attempt: u32, err: &Error) -> Option<Duration> {
+        match err.retry_after() {
+            Some(delay) if self.retry_after => (delay <= self.max_delay).then_some(delay),
+            _ => Some(self.backoff(attempt)),
+        }
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = self.multiplier.saturating_pow(attempt.saturating_sub(1));
+        let delay = self.base_delay.saturating_mul(factor).min(self.max_delay);
+        if self.jitter {
+            delay / 2 + delay.mul_f64(random() / 2.0)
+        } else {
+            delay
+        }
+    }
+}
+
+/// A number in `[0, 1)`, good enough to spread retries.
+fn random() -> f64 {
+    use std::{
+        collections::hash_map::RandomState,
+        hash::{BuildHasher as _, Hasher as _},
+    };
+    (RandomState::new().build_hasher().finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ErrorResponse;
+
+    fn throttled(retry_after: Option<Duration>) -> Error {
+        let status = StatusCode::TOO_MANY_REQUESTS;
+        let response = Box::new(ErrorResponse { status, retry_after, body: String::new() });
+        Error::Throttled { uri: "http://169.254.169.254/".parse().unwrap(), response }
+    }
+
+    fn timeout() -> Error {
+        Error::Timeout { uri: "http://169.254.169.254/".parse().unwrap() }
+    }
+
+    #[test]
+    fn backoff() {
+        let policy = RetryPolicy::default().jitter(false).max_delay(Duration::from_millis(300));
+        let delays: Vec<_> = (1..=4).map(|attempt| policy.delay(attempt, &timeout())).collect();
+        let ms = Duration::from_millis;
+        assert_eq!(delays, [Some(ms(100)), Some(ms(200)), Some(ms(300)), Some(ms(300))]);
+    }
+
+    #[test]
+    fn jitter() {
+        let policy = RetryPolicy::default();
+        for _ in 0..100 {
+            let delay = policy.delay(2, &timeout()).unwrap();
+            assert!(delay >= Duration::from_millis(100) && delay <= Duration::from_millis(200));
+        }
+    }
+
+    #[test]
+    fn retry_after() {
+        let policy = RetryPolicy::default().jitter(false);
+        let secs = Duration::from_secs;
+        assert_eq!(policy.delay(1, &throttled(Some(secs(2)))), Some(secs(2)));
+        assert_eq!(policy.delay(1, &throttled(None)), Some(Duration::from_millis(100)));
+        // A longer wait than `max_delay` is not retried.
+        assert_eq!(policy.delay(1, &throttled(Some(secs(6)))), None);
+
+        let policy = policy.respect_retry_after(false);
+        assert_eq!(policy.delay(1, &throttled(Some(secs(6)))), Some(Duration::from_millis(100)));
+    }
+
+    #[test]
+    fn should_retry() {
+        let policy = RetryPolicy::default();
+        assert!(policy.should_retry(&throttled(None)));
+        assert!(policy.should_retry(&timeout()));
+        let not_found = Error::NotFound { uri: "http://169.254.169.254/".parse().unwrap() };
+        assert!(!policy.should_retry(&not_found));
+        let policy = policy.retryable_statuses([StatusCode::SERVICE_UNAVAILABLE]);
+        assert!(!policy.should_retry(&throttled(None)));
+    }
+}
@@ -0,0 +1,138 @@
+//! The async runtime the client spawns tasks and waits on.
+
+use std::{fmt, future::Future, sync::Arc, time::Duration};
+
+use tokio::sync::{oneshot, watch};
+
+use crate::{BoxFuture, Cache};
+
+/// An async runtime for the [`Client`](crate::Client) to spawn tasks and wait on, set with
+/// [`ClientBuilder::runtime`](crate::ClientBuilder::runtime).
+///
+/// The client uses tokio by default. Implement this to run it on another executor, such as
+/// async-std or smol.
+pub trait Runtime: Send + Sync {
+    /// Run `fut` in the background.
+    fn spawn(&self, fut: BoxFuture<'static, ()>);
+
+    /// Return a future that completes after `duration`.
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()>;
+
+    /// Run `f`, which may block, without blocking the executor.
+    ///
+    /// Runs `f` on a new thread by default.
+    fn spawn_blocking(&self, f: Box<dyn FnOnce() + Send>) {
+        std::thread::spawn(f);
+    }
+}
+
+/// The runtime in use, tokio unless another one is set.
+#[derive(Clone, Default)]
+pub(crate) enum Rt {
+    #[default]
+    Tokio,
+    Custom(Arc<dyn Runtime>),
+}
+
+impl Rt {
+    /// Run `fut` in the background and return a future for its output, `None` if the task is
+    /// dropped before it completes.
+    ///
+    /// The task keeps running if the returned future is dropped.
+    pub(crate) fn spawn<T>(
+        &self,
+        fut: impl Future<Output = T> + Send + 'static,
+    ) -> impl Future<Output = Option<T>> + Send + 'static
+    where
+        T: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        let task = async move {
+            let _ = tx.send(fut.await);
+        };
+        match self {
+            Rt::Tokio => drop(tokio::spawn(task)),
+            Rt::Custom(rt) => rt.spawn(Box::pin(task)),
+        }
+        // the sender is dropped without sending if the task panicked or the executor dropped it
+        async move { rx.await.ok() }
+    }
+
+    /// Run the blocking `f` without blocking the executor and return a future for its output.
+    pub(crate) fn spawn_blocking<T>(
+        &self,
+        f: impl FnOnce() -> T + Send + 'static,
+    ) -> impl Future<Output = Option<T>> + Send + 'static
+    where
+        T: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        let f = move || {
+            let _ = tx.send(f());
+        };
+        match self {
+            Rt::Tokio => drop(tokio::task::spawn_blocking(f)),
+            Rt::Custom(rt) => rt.spawn_blocking(Box::new(f)),
+        }
+        async move { rx.await.ok() }
+    }
+
+    pub(crate) async fn sleep(&self, duration: Duration) {
+        match self {
+            Rt::Tokio => tokio::time::sleep(duration).await,
+            Rt::Custom(rt) => rt.sleep(duration).await,
+        }
+    }
+
+    /// Wait for `fut`, or return `None` if it does not complete within `duration`.
+    pub(crate) async fn timeout<F: Future>(&self, duration: Duration, fut: F) -> Option<F::Output> {
+        match self {
+            Rt::Tokio => tokio::time::timeout(duration, fut).await.ok(),
+            Rt::Custom(rt) => tokio::select! {
+                output = fut => Some(output),
+                _ = rt.sleep(duration) => None,
+            },
+        }
+    }
+}
+
+/// A handle to the task started by [`Client::spawn_refresh`](crate::Client::spawn_refresh).
+///
+/// Dropping the handle aborts the task.
+#[must_use = "dropping the handle stops the refresh"]
+pub struct RefreshHandle {
+    pub(crate) abort: watch::Sender<bool>,
+    pub(crate) cache: Arc<Cache>,
+    pub(crate) paths: Vec<String>,
+}
+
+impl RefreshHandle {
+    /// Stop the task and forget the values it kept, so that they are requested again. A
+    /// refresh in progress is completed first.
+    pub fn abort(&self) {
+        let _ = self.abort.send(true);
+        self.cache.unpin(&self.paths);
+    }
+}
+
+impl Drop for RefreshHandle {
+    fn drop(&mut self) {
+        self.abort();
+    }
+}
+
+impl fmt::Debug for RefreshHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RefreshHandle").field("paths", &self.paths).finish()
+    }
+}
+
+/// Complete once [`RefreshHandle::abort`](RefreshHandle::abort) is called or the handle is
+/// dropped.
+pub(crate) async fn aborted(rx: &mut watch::Receiver<bool>) {
+    loop {
+        if *rx.borrow_and_update() || rx.changed().await.is_err() {
+            return;
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// The values cached by a [`Client`](crate::Client), for running without the metadata service.
+///
+/// A snapshot is taken with [`Client::snapshot`](crate::Client::snapshot) and loaded into a new
+/// client with [`ClientBuilder::snapshot`](crate::ClientBuilder::snapshot). It serializes to
+/// JSON like
+///
+/// ```json
+/// {
+///   "on_gce": true,
+///   "values": {
+///     "/computeMetadata/v1/project/project-id": "my-project"
+///   }
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// The result of [`Client::on_gce`](crate::Client::on_gce), if it was probed.
+    #[serde(default)]
+    pub on_gce: Option<bool>,
+    /// The response bodies keyed by path and query, such as
+    /// `/computeMetadata/v1/instance/zone`.
+    #[serde(default)]
+    pub values: BTreeMap<String, String>,
+}
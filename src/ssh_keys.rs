@@ -0,0 +1,131 @@
+use serde::Deserialize;
+
+/// An entry of the `ssh-keys` project or instance attribute.
+///
+/// Each line of the attribute has the form `<username>:<key_type> <key> [<comment>]`. Keys added
+/// by Google tooling may instead carry an expiration as
+/// `google-ssh {"userName":"<email>","expireOn":"<timestamp>"}` in place of the comment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SshKey {
+    /// The user on the VM the key grants access to.
+    pub username: String,
+    /// The key type, such as `ssh-rsa` or `ssh-ed25519`.
+    pub key_type: String,
+    /// The base64 encoded public key.
+    pub key: String,
+    /// The trailing comment, or the `userName` of an expiring key.
+    pub comment: Option<String>,
+    /// The expiration timestamp of an expiring key.
+    pub expire_on: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Expiry {
+    user_name: Option<String>,
+    expire_on: Option<String>,
+}
+
+impl SshKey {
+    /// Parse a single line of an `ssh-keys` attribute.
+    ///
+    /// Returns `None` if the line is not a valid entry.
+    pub fn parse(line: &str) -> Option<Self> {
+        let (username, rest) = line.trim().split_once(':')?;
+        let (key_type, rest) = next_field(rest)?;
+        let (key, rest) = next_field(rest)?;
+        // The comment keeps its inner whitespace, the expiry JSON may contain some.
+        let comment = Some(rest.trim()).filter(|s| !s.is_empty());
+
+        let (comment, expire_on) = match comment {
+            Some(c) if c.starts_with("google-ssh ") => {
+                let expiry: Expiry = serde_json::from_str(c["google-ssh ".len()..].trim()).ok()?;
+                (expiry.user_name, expiry.expire_on)
+            }
+            c => (c.map(ToOwned::to_owned), None),
+        };
+
+        Some(Self {
+            username: username.trim().to_owned(),
+            key_type: key_type.to_owned(),
+            key: key.to_owned(),
+            comment,
+            expire_on,
+        })
+    }
+}
+
+/// Split the first whitespace separated field off `s`.
+fn next_field(s: &str) -> Option<(&str, &str)> {
+    let s = s.trim_start();
+    let end = s.find(char::is_whitespace).unwrap_or(s.len());
+    Some(s.split_at(end)).filter(|(field, _)| !field.is_empty())
+}
+
+pub(crate) fn parse_ssh_keys(s: &str) -> Vec<SshKey> {
+    s.lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| {
+            let key = SshKey::parse(line);
+            if key.is_none() {
+                tracing::trace!("skip invalid ssh-keys entry");
+            }
+            key
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            SshKey::parse("bob:ssh-ed25519  AAAA  c d"),
+            Some(SshKey {
+                username: "bob".into(),
+                key_type: "ssh-ed25519".into(),
+                key: "AAAA".into(),
+                comment: Some("c d".into()),
+                expire_on: None,
+            })
+        );
+        assert_eq!(
+            SshKey::parse("bob:ssh-rsa\tAAAA"),
+            Some(SshKey {
+                username: "bob".into(),
+                key_type: "ssh-rsa".into(),
+                key: "AAAA".into(),
+                comment: None,
+                expire_on: None,
+            })
+        );
+        assert_eq!(SshKey::parse("bob:ssh-rsa"), None);
+        assert_eq!(SshKey::parse("bob:ssh-rsa  "), None);
+        assert_eq!(SshKey::parse("ssh-rsa AAAA bob"), None);
+    }
+
+    #[test]
+    fn parse_expiring() {
+        assert_eq!(
+            SshKey::parse(
+                r#"bob:ecdsa-sha2-nistp256 AAAA google-ssh {"userName": "bob@example.com", "expireOn": "2024-01-01T00:00:00+0000"}"#
+            ),
+            Some(SshKey {
+                username: "bob".into(),
+                key_type: "ecdsa-sha2-nistp256".into(),
+                key: "AAAA".into(),
+                comment: Some("bob@example.com".into()),
+                expire_on: Some("2024-01-01T00:00:00+0000".into()),
+            })
+        );
+        assert_eq!(SshKey::parse("bob:ssh-rsa AAAA google-ssh {"), None);
+    }
+
+    #[test]
+    fn parse_lines() {
+        let keys = parse_ssh_keys("alice:ssh-rsa AAAA a\n\ninvalid\nbob:ssh-ed25519 BBBB\n");
+        assert_eq!(keys.iter().map(|k| k.username.as_str()).collect::<Vec<_>>(), ["alice", "bob"]);
+    }
+}
@@ -0,0 +1,55 @@
+//! Response bodies read as they arrive, see [`Client::get_stream`](crate::Client::get_stream).
+
+use std::{
+    fmt,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use futures_core::Stream;
+use hyper::{
+    body::{Body as _, Bytes},
+    Uri,
+};
+
+use crate::{transport::BoxBody, Error};
+
+/// The body of a response, yielded chunk by chunk as it is received.
+pub struct BodyStream {
+    uri: Uri,
+    body: BoxBody,
+}
+
+impl BodyStream {
+    pub(crate) fn new(uri: Uri, body: BoxBody) -> Self {
+        Self { uri, body }
+    }
+}
+
+impl fmt::Debug for BodyStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BodyStream").field("uri", &self.uri).finish()
+    }
+}
+
+impl Stream for BodyStream {
+    type Item = crate::Result<Bytes>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match ready!(Pin::new(&mut self.body).poll_frame(cx)) {
+                Some(Ok(frame)) => {
+                    // trailers carry no data, the metadata service does not send them anyway
+                    if let Ok(data) = frame.into_data() {
+                        return Poll::Ready(Some(Ok(data)));
+                    }
+                }
+                Some(Err(source)) => {
+                    let uri = self.uri.clone();
+                    return Poll::Ready(Some(Err(Error::Transport { uri, source })));
+                }
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
@@ -0,0 +1,73 @@
+//! HTTPS connectors, for metadata hosts fronted by a TLS-terminating proxy.
+//!
+//! The connectors still speak plain HTTP to `http` URIs, so a client built with them reaches
+//! the metadata IP as usual unless [`ClientBuilder::scheme`](crate::ClientBuilder::scheme)
+//! selects `https`.
+
+use hyper_util::client::legacy::connect::HttpConnector;
+
+use crate::Error;
+
+/// A client certificate chain and its private key, both PEM encoded.
+#[derive(Clone)]
+pub(crate) struct Identity {
+    pub(crate) cert: Vec<u8>,
+    pub(crate) key: Vec<u8>,
+}
+
+/// Wrap `http` in a rustls connector trusting the platform's root certificates.
+#[cfg(feature = "rustls")]
+pub(crate) fn rustls(
+    mut http: HttpConnector,
+    identity: Option<&Identity>,
+) -> crate::Result<hyper_rustls::HttpsConnector<HttpConnector>> {
+    use hyper_rustls::ConfigBuilderExt as _;
+    use rustls::pki_types::{pem::PemObject as _, CertificateDer, PrivateKeyDer};
+
+    let fail = |what: &str, e: &dyn std::fmt::Display| Error::Config {
+        reason: format!("failed to {}: {}", what, e),
+    };
+    let provider = std::sync::Arc::new(rustls::crypto::ring::default_provider());
+    let builder = rustls::ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(|e| fail("configure rustls", &e))?
+        .with_native_roots()
+        .map_err(|e| fail("load the native root certificates", &e))?;
+    let config = match identity {
+        Some(identity) => {
+            let certs = CertificateDer::pem_slice_iter(&identity.cert)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| fail("read the client certificate", &e))?;
+            let key = PrivateKeyDer::from_pem_slice(&identity.key)
+                .map_err(|e| fail("read the client key", &e))?;
+            builder
+                .with_client_auth_cert(certs, key)
+                .map_err(|e| fail("use the client identity", &e))?
+        }
+        None => builder.with_no_client_auth(),
+    };
+
+    http.enforce_http(false);
+    let builder = hyper_rustls::HttpsConnectorBuilder::new().with_tls_config(config);
+    Ok(builder.https_or_http().enable_http1().wrap_connector(http))
+}
+
+/// Wrap `http` in a connector using the system TLS stack and its trust store.
+#[cfg(feature = "native-tls")]
+pub(crate) fn native_tls(
+    mut http: HttpConnector,
+    identity: Option<&Identity>,
+) -> crate::Result<hyper_tls::HttpsConnector<HttpConnector>> {
+    let mut builder = native_tls::TlsConnector::builder();
+    if let Some(identity) = identity {
+        let identity = native_tls::Identity::from_pkcs8(&identity.cert, &identity.key)
+            .map_err(|e| Error::Config { reason: format!("invalid client identity: {}", e) })?;
+        builder.identity(identity);
+    }
+    let tls = builder.build().map_err(|e| Error::Config {
+        reason: format!("failed to create the native TLS connector: {}", e),
+    })?;
+
+    http.enforce_http(false);
+    Ok(hyper_tls::HttpsConnector::from((http, tls.into())))
+}
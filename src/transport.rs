@@ -0,0 +1,185 @@
+//! The HTTP client requests are sent with.
+//!
+//! Every request goes through [`HttpTransport`](HttpTransport), so that the rest of the crate
+//! does not depend on the details of the HTTP library.
+//!
+//! The hyper-util client is the only implementation and is always built: the types of the `http`
+//! crate re-exported by hyper are part of the public API, and a Client is always created from a
+//! hyper connector or client.
+
+use std::{any::Any, error, sync::Arc};
+
+use http_body_util::{combinators::UnsyncBoxBody, BodyExt as _};
+use hyper::{
+    body::{Body, Bytes},
+    http::request,
+    Request, Response,
+};
+use hyper_util::client::legacy::{self, connect::Connect};
+
+use crate::BoxFuture;
+
+pub(crate) type BoxError = Box<dyn error::Error + Send + Sync>;
+
+/// A response body that is not read yet.
+pub(crate) type BoxBody = UnsyncBoxBody<Bytes, BoxError>;
+
+/// A hook run around every request sent to the metadata service, set with
+/// [`ClientBuilder::interceptor`](crate::ClientBuilder::interceptor).
+///
+/// Meant for custom logging, header changes or fault injection in tests. Only the requests
+/// that are actually sent are seen, not the values answered from the cache.
+pub trait Interceptor: Send + Sync {
+    /// Inspect or change a request before it is sent.
+    ///
+    /// An error fails the request with [`Error::Transport`](crate::Error::Transport) without
+    /// sending it.
+    fn on_request(
+        &self,
+        request: &mut request::Parts,
+    ) -> Result<(), Box<dyn error::Error + Send + Sync>> {
+        let _ = request;
+        Ok(())
+    }
+
+    /// Inspect or change a response, whose body is already read, before the client handles it.
+    ///
+    /// Not called for the responses of [`Client::get_stream`](crate::Client::get_stream),
+    /// whose body is read by the caller.
+    fn on_response(&self, request: &request::Parts, response: &mut Response<Bytes>) {
+        let _ = (request, response);
+    }
+}
+
+/// Sends a request and reads the whole response body.
+pub(crate) trait HttpTransport<B> {
+    fn send(&self, req: Request<B>) -> BoxFuture<'static, Result<Response<Bytes>, BoxError>>;
+
+    /// Sends a request and leaves the response body to the caller.
+    fn send_streaming(
+        &self,
+        req: Request<B>,
+    ) -> BoxFuture<'static, Result<Response<BoxBody>, BoxError>>;
+
+    /// The underlying HTTP client, to hand it out to users.
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<C, B> HttpTransport<B> for legacy::Client<C, B>
+where
+    C: Connect + Clone + Send + Sync + 'static,
+    B: Body + Send + Unpin + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+{
+    fn send(&self, req: Request<B>) -> BoxFuture<'static, Result<Response<Bytes>, BoxError>> {
+        let fut = self.request(req);
+        Box::pin(async move {
+            let (parts, body) = fut.await?.into_parts();
+            let body = body.collect().await?.to_bytes();
+            let resp = Response::from_parts(parts, body);
+            #[cfg(feature = "gzip")]
+            let resp = decode_gzip(resp)?;
+            Ok(resp)
+        })
+    }
+
+    fn send_streaming(
+        &self,
+        req: Request<B>,
+    ) -> BoxFuture<'static, Result<Response<BoxBody>, BoxError>> {
+        let fut = self.request(req);
+        Box::pin(async move {
+            let resp = fut.await?;
+            Ok(resp.map(|body| body.map_err(Into::into).boxed_unsync()))
+        })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Decompress a body sent with `Content-Encoding: gzip`.
+#[cfg(feature = "gzip")]
+fn decode_gzip(mut resp: Response<Bytes>) -> Result<Response<Bytes>, BoxError> {
+    use hyper::header::{CONTENT_ENCODING, CONTENT_LENGTH};
+    use std::io::Read as _;
+
+    let gzip = resp
+        .headers()
+        .get(CONTENT_ENCODING)
+        .is_some_and(|value| value.as_bytes().eq_ignore_ascii_case(b"gzip"));
+    if gzip {
+        let mut body = Vec::new();
+        flate2::read::GzDecoder::new(&resp.body()[..]).read_to_end(&mut body)?;
+        resp.headers_mut().remove(CONTENT_ENCODING);
+        resp.headers_mut().remove(CONTENT_LENGTH);
+        *resp.body_mut() = Bytes::from(body);
+    }
+    Ok(resp)
+}
+
+/// Runs the interceptors, in the order they were added, around the requests sent by `inner`.
+pub(crate) struct Intercepted<'a, T: ?Sized> {
+    pub(crate) inner: &'a T,
+    pub(crate) interceptors: &'a [Arc<dyn Interceptor>],
+}
+
+impl<B, T> HttpTransport<B> for Intercepted<'_, T>
+where
+    T: HttpTransport<B> + ?Sized,
+{
+    fn send(&self, req: Request<B>) -> BoxFuture<'static, Result<Response<Bytes>, BoxError>> {
+        let (mut parts, body) = req.into_parts();
+        if let Err(e) = self.on_request(&mut parts) {
+            return Box::pin(async move { Err(e) });
+        }
+        self.send_intercepted(parts, body)
+    }
+
+    fn send_streaming(
+        &self,
+        req: Request<B>,
+    ) -> BoxFuture<'static, Result<Response<BoxBody>, BoxError>> {
+        let (mut parts, body) = req.into_parts();
+        if let Err(e) = self.on_request(&mut parts) {
+            return Box::pin(async move { Err(e) });
+        }
+        self.inner.send_streaming(Request::from_parts(parts, body))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self.inner.as_any()
+    }
+}
+
+impl<T: ?Sized> Intercepted<'_, T> {
+    /// Run the `on_request` hooks, the request must not be sent if one of them fails.
+    pub(crate) fn on_request(&self, parts: &mut request::Parts) -> Result<(), BoxError> {
+        self.interceptors.iter().try_for_each(|interceptor| interceptor.on_request(parts))
+    }
+
+    /// Send a request the `on_request` hooks already ran on, and run the `on_response` hooks.
+    pub(crate) fn send_intercepted<B>(
+        &self,
+        parts: request::Parts,
+        body: B,
+    ) -> BoxFuture<'static, Result<Response<Bytes>, BoxError>>
+    where
+        T: HttpTransport<B>,
+    {
+        if self.interceptors.is_empty() {
+            return self.inner.send(Request::from_parts(parts, body));
+        }
+        let fut = self.inner.send(Request::from_parts(parts.clone(), body));
+        let interceptors = self.interceptors.to_vec();
+        Box::pin(async move {
+            let mut resp = fut.await?;
+            for interceptor in &interceptors {
+                interceptor.on_response(&parts, &mut resp);
+            }
+            Ok(resp)
+        })
+    }
+}
@@ -0,0 +1,46 @@
+//! A connector for metadata emulators and workload identity sidecars listening on a Unix
+//! domain socket.
+
+use std::{
+    io,
+    path::Path,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use hyper::Uri;
+use hyper_util::rt::TokioIo;
+use tokio::net::UnixStream;
+
+use crate::BoxFuture;
+
+/// A connector opening every connection to the Unix domain socket at a fixed path, whatever
+/// the host of the request.
+///
+/// Used by [`ClientBuilder::build_with_unix_socket`](crate::ClientBuilder::build_with_unix_socket).
+#[derive(Debug, Clone)]
+pub struct UnixConnector {
+    path: Arc<Path>,
+}
+
+impl UnixConnector {
+    /// Create a connector to the socket at `path`.
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self { path: Arc::from(path.as_ref()) }
+    }
+}
+
+impl tower_service::Service<Uri> for UnixConnector {
+    type Response = TokioIo<UnixStream>;
+    type Error = io::Error;
+    type Future = BoxFuture<'static, io::Result<Self::Response>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _: Uri) -> Self::Future {
+        let path = self.path.clone();
+        Box::pin(async move { UnixStream::connect(&*path).await.map(TokioIo::new) })
+    }
+}
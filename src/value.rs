@@ -0,0 +1,25 @@
+//! Values returned together with what is known about them.
+
+/// A value of the metadata service and the `ETag` it was sent with, see
+/// [`Client::get_with_etag`](crate::Client::get_with_etag).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValueWithEtag {
+    /// The value.
+    pub value: String,
+    /// The `ETag` of the value, `None` if the response had none.
+    ///
+    /// Passed as `last_etag` to a `wait_for_change=true` request, it makes the request return
+    /// as soon as the value differs from this one, even if it changed in between.
+    pub etag: Option<String>,
+}
+
+/// A value of the metadata service that may be stale, see
+/// [`Client::get_with_staleness`](crate::Client::get_with_staleness).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaybeStale<T> {
+    /// The value.
+    pub value: T,
+    /// Whether the value is the last one received for the path, served because the request
+    /// for a live one failed.
+    pub stale: bool,
+}
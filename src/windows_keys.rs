@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+
+/// An entry of the `windows-keys` instance attribute.
+///
+/// The guest agent on a Windows VM watches this attribute and, for each new entry, resets the
+/// password of `user_name` and reports it encrypted with the given RSA public key.
+///
+/// See [`Automate password generation`][`doc`] for the full workflow.
+///
+/// [`doc`]: https://cloud.google.com/compute/docs/instances/windows/automate-pw-generation
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowsKey {
+    /// The Windows account to reset.
+    pub user_name: String,
+    /// The base64 encoded modulus of the RSA public key.
+    pub modulus: String,
+    /// The base64 encoded exponent of the RSA public key.
+    pub exponent: String,
+    /// The email of the user requesting the reset.
+    pub email: String,
+    /// The RFC 3339 timestamp after which the agent ignores this entry.
+    pub expire_on: String,
+    /// The hash function used for OAEP padding, such as `sha256`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash_function: Option<String>,
+    /// Whether the account should be added to the `Administrators` group.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub add_to_administrators: Option<bool>,
+    /// The length of the generated password.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password_length: Option<u32>,
+}
+
+impl WindowsKey {
+    /// Serialize this entry into the single-line JSON form stored in the `windows-keys` attribute.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+}
+
+/// The response written by the guest agent after handling a [`WindowsKey`](WindowsKey).
+///
+/// The agent prints one JSON document per line to serial port 4 (`COM4`).
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowsCredentials {
+    /// The Windows account that was reset.
+    #[serde(default)]
+    pub user_name: String,
+    /// Whether a password was generated.
+    #[serde(default)]
+    pub password_found: bool,
+    /// The base64 encoded password encrypted with the requested RSA public key.
+    #[serde(default)]
+    pub encrypted_password: String,
+    /// The modulus of the RSA public key used for encryption.
+    #[serde(default)]
+    pub modulus: String,
+    /// The exponent of the RSA public key used for encryption.
+    #[serde(default)]
+    pub exponent: String,
+    /// The hash function used for OAEP padding.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash_function: Option<String>,
+    /// The error reported by the agent, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_message: Option<String>,
+}
+
+impl WindowsCredentials {
+    /// Parse a response line written by the guest agent.
+    pub fn from_json(s: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(s)
+    }
+}
+
+pub(crate) fn parse_windows_keys(s: &str) -> Vec<WindowsKey> {
+    s.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .filter_map(|line| match serde_json::from_str(line) {
+            Ok(key) => Some(key),
+            Err(e) => {
+                tracing::trace!("skip invalid windows-keys entry: {}", e);
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_lines() {
+        let s = concat!(
+            r#"{"userName":"alice","modulus":"AQAB","exponent":"AQAB","email":"a@example.com","expireOn":"2024-01-01T00:00:00Z"}"#,
+            "\n\n",
+            "not json\n",
+            r#"  {"userName":"bob","modulus":"m","exponent":"e","email":"b@example.com","expireOn":"x","hashFunction":"sha256","addToAdministrators":true,"passwordLength":20}  "#,
+            "\n",
+        );
+        let keys = parse_windows_keys(s);
+        assert_eq!(keys.len(), 2);
+        assert_eq!(keys[0].user_name, "alice");
+        assert_eq!(keys[0].hash_function, None);
+        assert_eq!(keys[1].hash_function.as_deref(), Some("sha256"));
+        assert_eq!(keys[1].add_to_administrators, Some(true));
+        assert_eq!(keys[1].password_length, Some(20));
+    }
+
+    #[test]
+    fn round_trip() {
+        let key = WindowsKey {
+            user_name: "alice".into(),
+            expire_on: "2024-01-01T00:00:00Z".into(),
+            ..Default::default()
+        };
+        let json = key.to_json().unwrap();
+        assert!(!json.contains("hashFunction"));
+        assert_eq!(parse_windows_keys(&json), [key]);
+    }
+}